@@ -4,11 +4,8 @@ use spin::Mutex;
 use volatile::Volatile;
 
 lazy_static! {
-    pub static ref WRITER: Mutex<Writer> = Mutex::new(Writer {
-        column_position: 0,
-        color_code: ColorCode::new(Color::Yellow, Color::Black),
-        buffer: unsafe {&mut *(0xb8000 as *mut Buffer) },
-    });
+    pub static ref WRITER: Mutex<Writer> =
+        Mutex::new(Writer::new(unsafe { &mut *(0xb8000 as *mut Buffer) }));
 }
 
 
@@ -40,9 +37,16 @@ pub enum Color {
 struct ColorCode(u8);
 
 impl ColorCode {
-    fn new(foreground: Color, background: Color) -> ColorCode {
+    const fn new(foreground: Color, background: Color) -> ColorCode {
         ColorCode((background as u8) << 4 | (foreground as u8))
     }
+
+    /* Like `new` but also sets the high blink bit (bit 7 of the attribute
+     * byte, i.e. bit 15 of the text-buffer cell) when `blink` is true */
+    #[allow(dead_code)]
+    const fn with_blink(foreground: Color, background: Color, blink: bool) -> ColorCode {
+        ColorCode(((blink as u8) << 7) | (background as u8) << 4 | (foreground as u8))
+    }
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -54,67 +58,308 @@ struct ScreenChar {
 
 const BUFFER_HEIGHT: usize = 25;
 const BUFFER_WIDTH: usize = 80;
+// rows of history retained above the viewport for paging
+const SCROLLBACK: usize = 64;
+
+// a cleared cell, reused to initialise the host-allocated history/snapshot
+const BLANK: ScreenChar = ScreenChar {
+    ascii_character: b' ',
+    color_code: ColorCode::new(Color::Yellow, Color::Black),
+};
 
 #[repr(transparent)]
 struct Buffer {
     chars: [[Volatile<ScreenChar>; BUFFER_WIDTH]; BUFFER_HEIGHT],
 }
 
+/* Tracks where the escape-sequence parser is between bytes. CSI sequences
+ * accumulate their decimal parameters into `params`, `count` slots wide,
+ * with the digits of the unfinished parameter held in `current`. */
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ParseState {
+    Normal,
+    Escape,
+    Csi { params: [u8; 4], count: usize, current: usize },
+}
+
 /* Always writes to last line and shifts lines up when lin is full or on \n */
 pub struct Writer {
-    column_position: usize, // tracks current position in the last row
+    column_position: usize, // tracks the cursor column within `row`
+    row: usize, // tracks the cursor row, normally the last line
     color_code: ColorCode,
+    parse_state: ParseState, // VT100 escape-sequence parser state
+    history: [[ScreenChar; BUFFER_WIDTH]; SCROLLBACK], // ring of scrolled-off rows
+    history_len: usize, // number of valid rows in the ring (<= SCROLLBACK)
+    history_head: usize, // next write slot in the ring
+    view_offset: usize, // rows scrolled up from the live view (0 = live)
+    snapshot: [[ScreenChar; BUFFER_WIDTH]; BUFFER_HEIGHT], // live view saved while paging
+    snapshot_valid: bool, // whether `snapshot` currently holds the live view
     buffer: &'static mut Buffer, // specifies reference is valid for whole program run time
 }
 
 /* Writes single ASCII byte */
 impl Writer {
+    /* Builds a writer over the given buffer. On hardware this is the VGA
+     * text buffer at `0xb8000`; host tests pass a heap-allocated `Buffer`. */
+    pub fn new(buffer: &'static mut Buffer) -> Writer {
+        Writer {
+            column_position: 0,
+            row: BUFFER_HEIGHT - 1,
+            color_code: ColorCode::new(Color::Yellow, Color::Black),
+            parse_state: ParseState::Normal,
+            history: [[BLANK; BUFFER_WIDTH]; SCROLLBACK],
+            history_len: 0,
+            history_head: 0,
+            view_offset: 0,
+            snapshot: [[BLANK; BUFFER_WIDTH]; BUFFER_HEIGHT],
+            snapshot_valid: false,
+            buffer,
+        }
+    }
+
+    /* Feeds one byte through the VT100 parser. In `Normal` state printable
+     * bytes and `\n` behave as before; `0x1b` opens an escape sequence that
+     * is consumed (not printed) until its final byte is dispatched. */
     pub fn write_byte(&mut self, byte: u8) {
-        match byte {
-            b'\n' => self.new_line(),// if \n do not print anything
-            byte => {
-                if self.column_position >= BUFFER_WIDTH {
-                    self.new_line();
+        match self.parse_state {
+            ParseState::Normal => match byte {
+                0x1b => self.parse_state = ParseState::Escape,
+                b'\n' => self.new_line(), // if \n do not print anything
+                byte => self.put_byte(byte),
+            },
+            ParseState::Escape => {
+                // only `ESC [` (CSI) is understood; anything else aborts
+                self.parse_state = if byte == b'[' {
+                    ParseState::Csi { params: [0; 4], count: 0, current: 0 }
+                } else {
+                    ParseState::Normal
+                };
+            }
+            ParseState::Csi { mut params, mut count, mut current } => match byte {
+                b'0'..=b'9' => {
+                    current = current.saturating_mul(10).saturating_add((byte - b'0') as usize);
+                    self.parse_state = ParseState::Csi { params, count, current };
+                }
+                b';' => {
+                    if count < params.len() {
+                        params[count] = current as u8;
+                        count += 1;
+                    }
+                    self.parse_state = ParseState::Csi { params, count, current: 0 };
+                }
+                // final byte in the CSI range dispatches the sequence
+                0x40..=0x7e => {
+                    if count < params.len() {
+                        params[count] = current as u8;
+                        count += 1;
+                    }
+                    self.parse_state = ParseState::Normal;
+                    self.dispatch_csi(byte, &params[..count]);
+                }
+                // malformed: drop back to Normal and print nothing
+                _ => self.parse_state = ParseState::Normal,
+            },
+        }
+    }
+
+    /* Puts a single already-translated byte at the cursor, wrapping to a
+     * new line when the current row is full */
+    fn put_byte(&mut self, byte: u8) {
+        self.snap_to_bottom();
+        if self.column_position >= BUFFER_WIDTH {
+            self.new_line();
+        }
+
+        let row = self.row;
+        let col = self.column_position;
+
+        let color_code = self.color_code;
+        self.buffer.chars[row][col].write(ScreenChar {
+            ascii_character: byte,
+            color_code,
+        });
+        self.column_position += 1;
+        self.update_cursor();
+    }
+
+    /* Moves the logical cursor to `(row, col)`, clamping both to the
+     * viewport, and repositions the hardware cursor to match */
+    pub fn set_cursor(&mut self, row: usize, col: usize) {
+        self.snap_to_bottom();
+        self.row = row.min(BUFFER_HEIGHT - 1);
+        self.column_position = col.min(BUFFER_WIDTH - 1);
+        self.update_cursor();
+    }
+
+    /* Moves the cursor one column left, stopping at the start of the row */
+    pub fn move_cursor_left(&mut self) {
+        self.snap_to_bottom();
+        if self.column_position > 0 {
+            self.column_position -= 1;
+            self.update_cursor();
+        }
+    }
+
+    /* Moves the cursor one column right, stopping at the last column */
+    pub fn move_cursor_right(&mut self) {
+        self.snap_to_bottom();
+        if self.column_position < BUFFER_WIDTH - 1 {
+            self.column_position += 1;
+            self.update_cursor();
+        }
+    }
+
+    /* Deletes the character left of the cursor, moving the cursor back and
+     * overwriting the vacated cell with a blank */
+    pub fn backspace(&mut self) {
+        self.snap_to_bottom();
+        if self.column_position == 0 {
+            return;
+        }
+        self.column_position -= 1;
+        let blank = ScreenChar {
+            ascii_character: b' ',
+            color_code: self.color_code,
+        };
+        self.buffer.chars[self.row][self.column_position].write(blank);
+        self.update_cursor();
+    }
+
+    /* Programs the VGA CRT controller so the blinking hardware cursor
+     * tracks the logical `(row, column_position)` position */
+    #[cfg(not(test))]
+    fn update_cursor(&self) {
+        let offset = (self.row * BUFFER_WIDTH + self.column_position) as u16;
+        unsafe {
+            // register 0x0F/0x0E hold the low/high byte of the cursor offset
+            outb(0x3d4, 0x0f);
+            outb(0x3d5, (offset & 0xff) as u8);
+            outb(0x3d4, 0x0e);
+            outb(0x3d5, (offset >> 8) as u8);
+        }
+    }
+
+    /* Host test builds have no VGA hardware to program */
+    #[cfg(test)]
+    fn update_cursor(&self) {}
+
+    /* Applies a completed CSI sequence: `m` updates the active color from
+     * SGR codes, `J` with param 2 clears the screen, and `H`/`f` move the
+     * logical cursor to the 1-based (row, col) in the parameters. */
+    fn dispatch_csi(&mut self, final_byte: u8, params: &[u8]) {
+        match final_byte {
+            b'm' => {
+                // an empty parameter list behaves like a lone reset
+                if params.is_empty() {
+                    self.apply_sgr(0);
+                } else {
+                    for &code in params {
+                        self.apply_sgr(code);
+                    }
                 }
-                
-                let row = BUFFER_HEIGHT - 1;
-                let col = self.column_position;
-
-                let color_code = self.color_code;
-                self.buffer.chars[row][col].write(ScreenChar {
-                    ascii_character: byte,
-                    color_code,
-                });
-                self.column_position += 1;
             }
+            b'J' => {
+                if params.first() == Some(&2) {
+                    self.clear_screen();
+                }
+            }
+            b'H' | b'f' => {
+                let row = params.first().copied().unwrap_or(1).max(1) as usize - 1;
+                let col = params.get(1).copied().unwrap_or(1).max(1) as usize - 1;
+                self.set_cursor(row, col);
+            }
+            _ => {}
         }
     }
 
-    /* Write provided ASCII string to buffer,
-     * wraps lines at BUFFER_WIDTH supporting `\n` character
-     * doesn't support strings with non-ASCII characters, can't be printed in VGA text mode
+    /* Updates the current `ColorCode` from a single SGR parameter, mapping
+     * the 16-color ANSI foreground/background codes onto the `Color` enum
+     * and leaving unknown codes untouched. */
+    fn apply_sgr(&mut self, code: u8) {
+        // VGA base (0-7) and bright (8-15) palette rows, ANSI order
+        const BASE: [Color; 8] = [
+            Color::Black, Color::Red, Color::Green, Color::Brown,
+            Color::Blue, Color::Magenta, Color::Cyan, Color::LightGray,
+        ];
+        const BRIGHT: [Color; 8] = [
+            Color::DarkGray, Color::LightRed, Color::LightGreen, Color::Yellow,
+            Color::LightBlue, Color::Pink, Color::LightCyan, Color::White,
+        ];
+        let attr = self.color_code.0;
+        self.color_code = match code {
+            0 => ColorCode::new(Color::LightGray, Color::Black),
+            30..=37 => ColorCode((attr & 0xf0) | BASE[(code - 30) as usize] as u8),
+            90..=97 => ColorCode((attr & 0xf0) | BRIGHT[(code - 90) as usize] as u8),
+            // VGA backgrounds are limited to the 8 base colors (bit 7 blinks)
+            40..=47 => ColorCode((attr & 0x8f) | ((BASE[(code - 40) as usize] as u8) << 4)),
+            100..=107 => ColorCode((attr & 0x8f) | ((BASE[(code - 100) as usize] as u8) << 4)),
+            _ => ColorCode(attr),
+        };
+    }
+
+    /* Clears the whole viewport and parks the cursor at the home column,
+     * first dropping back to the live view if the user was paging */
+    fn clear_screen(&mut self) {
+        self.snap_to_bottom();
+        for row in 0..BUFFER_HEIGHT {
+            self.clear_row(row);
+        }
+        self.column_position = 0;
+        self.row = 0;
+        self.update_cursor();
+    }
+
+    /* Write provided string to buffer,
+     * wraps lines at BUFFER_WIDTH supporting `\n` character.
+     * Printable ASCII takes a fast path; other Unicode scalars are
+     * translated to their code page 437 byte when the VGA hardware can
+     * display them, falling back to `0xfe` for unmappable characters.
      */
     fn write_string(&mut self, s: &str) {
-        for byte in s.bytes() {
-            match byte {
-                // ASCII byte or newline printable
-                0x20..=0x7e | b'\n' => self.write_byte(byte),
-                // not printable ASCII range
-                _ =>self.write_byte(0xfe),
+        for c in s.chars() {
+            match c {
+                // printable ASCII, newline and the escape introducer map straight through
+                '\n' => self.write_byte(b'\n'),
+                '\u{1b}' => self.write_byte(0x1b),
+                ' '..='~' => self.write_byte(c as u8),
+                // anything else routes through the CP437 table
+                _ => self.write_byte(cp437(c)),
             }
         }
     }
     
-    /* Shifts all lines up by one and clears last row */
-    fn new_line(&mut self) { 
+    /* Shifts all lines up by one and clears last row, pushing the line that
+     * scrolls off the top into the scrollback ring first */
+    fn new_line(&mut self) {
+        self.snap_to_bottom();
+        let mut evicted = [BLANK; BUFFER_WIDTH];
+        for col in 0..BUFFER_WIDTH {
+            evicted[col] = self.buffer.chars[0][col].read();
+        }
+        self.push_history(evicted);
         for row in 1..BUFFER_HEIGHT {
-            for col in 0..BUFFER_HEIGHT {
+            for col in 0..BUFFER_WIDTH {
                 let character = self.buffer.chars[row][col].read();
                 self.buffer.chars[row-1][col].write(character);
             }
         }
         self.clear_row(BUFFER_HEIGHT - 1);
         self.column_position = 0;
+        self.row = BUFFER_HEIGHT - 1;
+        self.update_cursor();
+    }
+
+    /* Replaces the current attribute's foreground/background, preserving
+     * the blink bit so subsequent writes use the new colors */
+    pub fn set_color(&mut self, foreground: Color, background: Color) {
+        let blink = self.color_code.0 & 0x80;
+        self.color_code = ColorCode(blink | ColorCode::new(foreground, background).0);
+    }
+
+    /* Toggles the blink bit on the current attribute without disturbing
+     * the foreground/background colors */
+    pub fn set_blink(&mut self, blink: bool) {
+        self.color_code = ColorCode((self.color_code.0 & 0x7f) | ((blink as u8) << 7));
     }
 
     /* Clears row. Overwrites with blank characters */
@@ -127,6 +372,199 @@ impl Writer {
             self.buffer.chars[row][col].write(blank);
         }
     }
+
+    /* Appends a scrolled-off row to the ring, dropping the oldest once full */
+    fn push_history(&mut self, row: [ScreenChar; BUFFER_WIDTH]) {
+        self.history[self.history_head] = row;
+        self.history_head = (self.history_head + 1) % SCROLLBACK;
+        if self.history_len < SCROLLBACK {
+            self.history_len += 1;
+        }
+    }
+
+    /* Returns the `logical`-th oldest retained row (0 == oldest) */
+    fn history_row(&self, logical: usize) -> &[ScreenChar; BUFFER_WIDTH] {
+        let start = if self.history_len < SCROLLBACK {
+            0
+        } else {
+            self.history_head
+        };
+        &self.history[(start + logical) % SCROLLBACK]
+    }
+
+    /* Scrolls the viewport `lines` rows towards older history, saving the
+     * live view the first time so it can be restored later */
+    pub fn scroll_up(&mut self, lines: usize) {
+        if !self.snapshot_valid {
+            self.save_snapshot();
+        }
+        self.view_offset = (self.view_offset + lines).min(self.history_len);
+        self.repaint();
+    }
+
+    /* Scrolls the viewport `lines` rows back towards the live view */
+    pub fn scroll_down(&mut self, lines: usize) {
+        // nothing to restore when already at the live view and no snapshot
+        // was ever taken — repainting here would paint blanks over the screen
+        if self.view_offset == 0 && !self.snapshot_valid {
+            return;
+        }
+        self.view_offset = self.view_offset.saturating_sub(lines);
+        self.repaint();
+        if self.view_offset == 0 {
+            self.snapshot_valid = false;
+        }
+    }
+
+    /* Copies the live viewport into `snapshot` so paging can overwrite the
+     * buffer without losing the current screen */
+    fn save_snapshot(&mut self) {
+        for row in 0..BUFFER_HEIGHT {
+            for col in 0..BUFFER_WIDTH {
+                self.snapshot[row][col] = self.buffer.chars[row][col].read();
+            }
+        }
+        self.snapshot_valid = true;
+    }
+
+    /* Restores the live viewport and drops back to it before new output */
+    fn snap_to_bottom(&mut self) {
+        if !self.snapshot_valid {
+            return;
+        }
+        for row in 0..BUFFER_HEIGHT {
+            for col in 0..BUFFER_WIDTH {
+                self.buffer.chars[row][col].write(self.snapshot[row][col]);
+            }
+        }
+        self.snapshot_valid = false;
+        self.view_offset = 0;
+    }
+
+    /* Repaints the 25-row viewport for the current `view_offset`, drawing
+     * older rows from the ring and the rest from the saved live view */
+    fn repaint(&mut self) {
+        let start = self.history_len - self.view_offset;
+        for i in 0..BUFFER_HEIGHT {
+            let logical = start + i;
+            let row = if logical < self.history_len {
+                *self.history_row(logical)
+            } else {
+                self.snapshot[logical - self.history_len]
+            };
+            for col in 0..BUFFER_WIDTH {
+                self.buffer.chars[i][col].write(row[col]);
+            }
+        }
+    }
+}
+
+/* Writes a byte to an x86 I/O port. Raw port access can clobber arbitrary
+ * hardware state, so callers must uphold that the port is safe to drive. */
+#[cfg(not(test))]
+unsafe fn outb(port: u16, value: u8) {
+    core::arch::asm!(
+        "out dx, al",
+        in("dx") port,
+        in("al") value,
+        options(nomem, nostack, preserve_flags),
+    );
+}
+
+/* Maps a Unicode scalar to its code page 437 byte for display in the VGA
+ * text buffer. The table is sorted by `char` so we can binary-search it;
+ * unmappable scalars fall back to the `0xfe` block glyph. */
+fn cp437(c: char) -> u8 {
+    // kept sorted by the `char` key for binary_search_by_key
+    const TABLE: &[(char, u8)] = &[
+        ('\u{00a0}', 0xFF), // no-break space
+        ('\u{00a7}', 0x15), // §
+        ('\u{00b0}', 0xF8), // °
+        ('\u{00b1}', 0xF1), // ±
+        ('\u{00b5}', 0xE6), // µ
+        ('\u{00c4}', 0x8E), // Ä
+        ('\u{00c5}', 0x8F), // Å
+        ('\u{00c6}', 0x92), // Æ
+        ('\u{00c9}', 0x90), // É
+        ('\u{00d6}', 0x99), // Ö
+        ('\u{00dc}', 0x9A), // Ü
+        ('\u{00df}', 0xE1), // ß
+        ('\u{00e0}', 0x85), // à
+        ('\u{00e1}', 0xA0), // á
+        ('\u{00e2}', 0x83), // â
+        ('\u{00e4}', 0x84), // ä
+        ('\u{00e5}', 0x86), // å
+        ('\u{00e6}', 0x91), // æ
+        ('\u{00e7}', 0x87), // ç
+        ('\u{00e8}', 0x8A), // è
+        ('\u{00e9}', 0x82), // é
+        ('\u{00ea}', 0x88), // ê
+        ('\u{00eb}', 0x89), // ë
+        ('\u{00ec}', 0x8D), // ì
+        ('\u{00ed}', 0xA1), // í
+        ('\u{00ee}', 0x8C), // î
+        ('\u{00ef}', 0x8B), // ï
+        ('\u{00f1}', 0xA4), // ñ
+        ('\u{00f2}', 0x95), // ò
+        ('\u{00f3}', 0xA2), // ó
+        ('\u{00f4}', 0x93), // ô
+        ('\u{00f6}', 0x94), // ö
+        ('\u{00f7}', 0xF6), // ÷
+        ('\u{00f9}', 0x97), // ù
+        ('\u{00fa}', 0xA3), // ú
+        ('\u{00fb}', 0x96), // û
+        ('\u{00fc}', 0x81), // ü
+        ('\u{00ff}', 0x98), // ÿ
+        ('\u{0393}', 0xE2), // Γ
+        ('\u{0398}', 0xE9), // Θ
+        ('\u{03a3}', 0xE4), // Σ
+        ('\u{03a6}', 0xE8), // Φ
+        ('\u{03a9}', 0xEA), // Ω
+        ('\u{03b1}', 0xE0), // α
+        ('\u{03b4}', 0xEB), // δ
+        ('\u{03c0}', 0xE3), // π
+        ('\u{03c3}', 0xE5), // σ
+        ('\u{03c4}', 0xE7), // τ
+        ('\u{03c6}', 0xED), // φ
+        ('\u{221e}', 0xEC), // ∞
+        ('\u{2248}', 0xF7), // ≈
+        ('\u{2500}', 0xC4), // ─
+        ('\u{2502}', 0xB3), // │
+        ('\u{250c}', 0xDA), // ┌
+        ('\u{2510}', 0xBF), // ┐
+        ('\u{2514}', 0xC0), // └
+        ('\u{2518}', 0xD9), // ┘
+        ('\u{251c}', 0xC3), // ├
+        ('\u{2524}', 0xB4), // ┤
+        ('\u{252c}', 0xC2), // ┬
+        ('\u{2534}', 0xC1), // ┴
+        ('\u{253c}', 0xC5), // ┼
+        ('\u{2550}', 0xCD), // ═
+        ('\u{2551}', 0xBA), // ║
+        ('\u{2554}', 0xC9), // ╔
+        ('\u{2557}', 0xBB), // ╗
+        ('\u{255a}', 0xC8), // ╚
+        ('\u{255d}', 0xBC), // ╝
+        ('\u{2560}', 0xCC), // ╠
+        ('\u{2563}', 0xB9), // ╣
+        ('\u{2566}', 0xCB), // ╦
+        ('\u{2569}', 0xCA), // ╩
+        ('\u{256c}', 0xCE), // ╬
+        ('\u{2580}', 0xDF), // ▀
+        ('\u{2584}', 0xDC), // ▄
+        ('\u{2588}', 0xDB), // █
+        ('\u{258c}', 0xDD), // ▌
+        ('\u{2590}', 0xDE), // ▐
+        ('\u{2591}', 0xB0), // ░
+        ('\u{2592}', 0xB1), // ▒
+        ('\u{2593}', 0xB2), // ▓
+        ('\u{25a0}', 0xFE), // ■
+    ];
+
+    match TABLE.binary_search_by_key(&c, |&(key, _)| key) {
+        Ok(idx) => TABLE[idx].1,
+        Err(_) => 0xFE,
+    }
 }
 
 /* prints whole strings, converting to bytes and printing one-by-one */
@@ -157,3 +595,92 @@ pub fn _print(args: fmt::Arguments) {
     WRITER.lock().write_fmt(args).unwrap();
 }
 
+/* Prints `args` without honoring the `WRITER` lock, for use from a panic
+ * handler where the lock may already be held (or the writer corrupted).
+ * Interrupts are disabled so nothing can race us, the lock is forced open,
+ * and the message is written in high-visibility White-on-Red on a cleared
+ * screen. This never blocks and is only safe to call once we are giving up. */
+pub fn print_emergency(args: fmt::Arguments) {
+    use core::fmt::Write;
+    unsafe {
+        // keep any interrupt handler from touching the buffer mid-write
+        core::arch::asm!("cli", options(nomem, nostack, preserve_flags));
+        // break any outstanding lock and take the writer unconditionally
+        WRITER.force_unlock();
+        let mut writer = WRITER.lock();
+        // abandon any half-received escape sequence so no byte is swallowed
+        writer.parse_state = ParseState::Normal;
+        writer.set_color(Color::White, Color::Red);
+        writer.clear_screen();
+        let _ = writer.write_fmt(args);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /* Builds a writer over a freshly-cleared, heap-allocated buffer */
+    fn construct_writer() -> Writer {
+        let buffer: &'static mut Buffer = Box::leak(Box::new(Buffer {
+            chars: [[Volatile::new(BLANK); BUFFER_WIDTH]; BUFFER_HEIGHT],
+        }));
+        Writer::new(buffer)
+    }
+
+    #[test]
+    fn write_string_wraps_at_width() {
+        let mut writer = construct_writer();
+        for _ in 0..BUFFER_WIDTH {
+            writer.write_string("a");
+        }
+        // the 81st character wraps onto a fresh line
+        writer.write_string("b");
+        assert_eq!(writer.column_position, 1);
+        assert_eq!(
+            writer.buffer.chars[BUFFER_HEIGHT - 1][0].read().ascii_character,
+            b'b'
+        );
+    }
+
+    #[test]
+    fn newline_advances_to_next_row() {
+        let mut writer = construct_writer();
+        writer.write_byte(b'x');
+        writer.write_byte(b'\n');
+        writer.write_byte(b'y');
+        let bottom = BUFFER_HEIGHT - 1;
+        assert_eq!(writer.buffer.chars[bottom][0].read().ascii_character, b'y');
+        assert_eq!(writer.buffer.chars[bottom - 1][0].read().ascii_character, b'x');
+    }
+
+    #[test]
+    fn scrolled_away_content_is_recoverable() {
+        let mut writer = construct_writer();
+        for i in 0..(BUFFER_HEIGHT * 2) {
+            writer.write_byte(b'a' + (i % 26) as u8);
+            writer.write_byte(b'\n');
+        }
+        // lines have scrolled off the top into the ring
+        assert!(writer.history_len > 0);
+        let live_top = writer.buffer.chars[0][0].read();
+        // scrolling all the way up repaints the oldest retained line
+        writer.scroll_up(writer.history_len);
+        assert_eq!(writer.buffer.chars[0][0].read(), writer.history_row(0)[0]);
+        // scrolling back down restores the live viewport
+        writer.scroll_down(writer.history_len);
+        assert_eq!(writer.buffer.chars[0][0].read(), live_top);
+    }
+
+    #[test]
+    fn scroll_down_on_live_view_leaves_viewport_unchanged() {
+        let mut writer = construct_writer();
+        writer.write_byte(b'z');
+        let before = writer.buffer.chars[BUFFER_HEIGHT - 1][0].read();
+        // paging down while already at the live view must not repaint blanks
+        writer.scroll_down(5);
+        assert_eq!(writer.buffer.chars[BUFFER_HEIGHT - 1][0].read(), before);
+        assert_eq!(before.ascii_character, b'z');
+    }
+}
+